@@ -5,34 +5,441 @@ use anchor_lang::prelude::*;
 
 declare_id!("TRUSTxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
+/// Maximum number of distinct oracles whose submissions are tracked per wallet.
+pub const MAX_SUBMISSIONS: usize = 8;
+
+/// Default freshness window (seconds) used when an account is first created.
+pub const DEFAULT_FRESHNESS_WINDOW: i64 = 3600;
+
+/// Default number of fresh submissions required before publishing an answer.
+pub const DEFAULT_MIN_SUBMISSIONS: u8 = 3;
+
+/// Space reserved for the registry's oracle list. `add_oracle` enforces the
+/// tighter `MAX_SUBMISSIONS` cap so the allowlist can never grow past the
+/// number of submission slots a `TrustScoreAccount` actually has.
+pub const MAX_ORACLES: usize = 16;
+
+/// Default staleness budget (seconds) used when an account is first created.
+pub const DEFAULT_MAX_STALENESS_SECS: i64 = 1800;
+
+/// Default window (seconds) an oracle has to heartbeat before it is
+/// considered to have gone silent.
+pub const DEFAULT_HEARTBEAT_INTERVAL: i64 = 300;
+
+/// Number of past (score, timestamp) samples retained per wallet.
+pub const HISTORY_LEN: usize = 16;
+
+/// Default half-life (seconds) used to time-decay historical samples.
+pub const DEFAULT_HALF_LIFE_SECS: i64 = 3600;
+
+/// Fixed deployer key allowed to bootstrap the oracle registry via
+/// `initialize_registry`. Swap for the real deployer key before deploying;
+/// this is what stops the first caller to touch the registry from
+/// front-running `add_oracle` and seizing admin control.
+pub const REGISTRY_BOOTSTRAP_AUTHORITY: Pubkey =
+    pubkey!("ADMINxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+
 #[program]
 pub mod trust_oracle {
     use super::*;
 
     /// Updates the on-chain trust score and risk level for a wallet.
-    /// Callable only by the account's oracle authority.
+    /// Callable only by the account's oracle authority, or by a registered
+    /// fallback oracle once the primary has gone stale and the fallback
+    /// itself is live (see `oracle_is_live`).
     pub fn update_trust_score(
         ctx: Context<UpdateTrustScore>,
         trust_score: u8,
         risk_level: RiskLevel,
+        confidence: u8,
     ) -> Result<()> {
-        let account = &mut ctx.accounts.trust_score_account;
+        let signer = ctx.accounts.oracle.key();
+        require!(
+            ctx.accounts.oracle_registry.oracles.contains(&signer),
+            TrustOracleError::UnauthorizedOracle
+        );
         require!(trust_score <= 100, TrustOracleError::InvalidTrustScore);
+        require!(confidence <= 100, TrustOracleError::InvalidConfidence);
+
+        let account = &mut ctx.accounts.trust_score_account;
+        if account.max_staleness_secs == 0 {
+            account.max_staleness_secs = DEFAULT_MAX_STALENESS_SECS;
+            account.half_life_secs = DEFAULT_HALF_LIFE_SECS;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let source = if signer == account.oracle_pubkey && account.oracle_pubkey != Pubkey::default()
+        {
+            OracleSource::Primary
+        } else if account.oracle_pubkey == Pubkey::default() {
+            // An account with a published aggregate already has a trustworthy
+            // median answer from `submit_score`; a single whitelisted oracle
+            // claiming primary here would silently overwrite it with its own
+            // unaggregated value. Only allow the claim before any aggregate
+            // has been published.
+            require!(!account.ready, TrustOracleError::ScoreAlreadyAggregated);
+            account.oracle_pubkey = signer;
+            OracleSource::Primary
+        } else if signer == account.fallback_oracle {
+            let primary_age = now - account.last_updated;
+            require!(
+                primary_age > account.max_staleness_secs,
+                TrustOracleError::UnauthorizedOracle
+            );
+            // A silent fallback is no safer than a silent primary: require it
+            // to have heartbeated, same as submissions do during aggregation.
+            require!(
+                oracle_is_live(ctx.remaining_accounts, &signer, now)?,
+                TrustOracleError::FallbackOracleNotLive
+            );
+            OracleSource::Fallback
+        } else {
+            return err!(TrustOracleError::UnauthorizedOracle);
+        };
+
         account.wallet_pubkey = ctx.accounts.wallet.key();
         account.trust_score = trust_score;
         account.risk_level = risk_level;
-        account.last_updated = Clock::get()?.unix_timestamp;
-        account.oracle_pubkey = ctx.accounts.oracle.key();
+        account.confidence = confidence;
+        account.last_updated = now;
+        account.oracle_source = source;
+        push_history(account, trust_score, now);
+        Ok(())
+    }
+
+    /// Sets (or replaces) the fallback oracle for a wallet's trust score
+    /// account. Only the current primary oracle may call this, and only
+    /// while it remains on the registry allowlist.
+    pub fn set_fallback_oracle(
+        ctx: Context<SetFallbackOracle>,
+        fallback_oracle: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts
+                .oracle_registry
+                .oracles
+                .contains(&ctx.accounts.oracle.key()),
+            TrustOracleError::UnauthorizedOracle
+        );
+        let account = &mut ctx.accounts.trust_score_account;
+        require!(
+            account.oracle_pubkey == ctx.accounts.oracle.key(),
+            TrustOracleError::UnauthorizedOracle
+        );
+        account.fallback_oracle = fallback_oracle;
+        Ok(())
+    }
+
+    /// Submits one oracle's view of a wallet's trust score. Each whitelisted
+    /// oracle owns a single slot in `submissions`; the published `trust_score`
+    /// is the median of the fresh slots once enough of them have reported.
+    pub fn submit_score(ctx: Context<SubmitScore>, score: u8) -> Result<()> {
+        require!(score <= 100, TrustOracleError::InvalidTrustScore);
+        require!(
+            ctx.accounts
+                .oracle_registry
+                .oracles
+                .contains(&ctx.accounts.oracle.key()),
+            TrustOracleError::UnauthorizedOracle
+        );
+
+        let account = &mut ctx.accounts.trust_score_account;
+        let oracle = ctx.accounts.oracle.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        if account.wallet_pubkey == Pubkey::default() {
+            account.wallet_pubkey = ctx.accounts.wallet.key();
+            account.freshness_window = DEFAULT_FRESHNESS_WINDOW;
+            account.min_submissions = DEFAULT_MIN_SUBMISSIONS;
+            account.max_staleness_secs = DEFAULT_MAX_STALENESS_SECS;
+            account.half_life_secs = DEFAULT_HALF_LIFE_SECS;
+        }
+
+        // Find the signer's existing slot, or the oldest slot if this oracle
+        // hasn't submitted yet.
+        let slot_index = account
+            .submissions
+            .iter()
+            .position(|s| s.oracle == oracle)
+            .unwrap_or_else(|| {
+                account
+                    .submissions
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, s)| s.ts)
+                    .map(|(i, _)| i)
+                    .unwrap_or(0)
+            });
+
+        account.submissions[slot_index] = Submission {
+            oracle,
+            value: score,
+            ts: now,
+        };
+
+        publish_aggregate(account, now, ctx.remaining_accounts)?;
+
+        Ok(())
+    }
+
+    /// Records liveness for the calling oracle, independent of any score
+    /// submission. Aggregation and fallback authority selection only
+    /// consider oracles that have heartbeated within `heartbeat_interval`.
+    pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+        require!(
+            ctx.accounts
+                .oracle_registry
+                .oracles
+                .contains(&ctx.accounts.oracle.key()),
+            TrustOracleError::UnauthorizedOracle
+        );
+
+        let status = &mut ctx.accounts.oracle_status;
+        if status.oracle == Pubkey::default() {
+            status.oracle = ctx.accounts.oracle.key();
+            status.heartbeat_interval = DEFAULT_HEARTBEAT_INTERVAL;
+        }
+        status.last_heartbeat = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Read-only view of an oracle's liveness; emits the current status so
+    /// clients can enumerate which oracles are live without replaying the
+    /// heartbeat math themselves.
+    pub fn get_oracle_status(ctx: Context<GetOracleStatus>) -> Result<()> {
+        let status = &ctx.accounts.oracle_status;
+        let now = Clock::get()?.unix_timestamp;
+        emit!(OracleStatusRead {
+            oracle: status.oracle,
+            last_heartbeat: status.last_heartbeat,
+            is_live: now - status.last_heartbeat <= status.heartbeat_interval,
+        });
         Ok(())
     }
 
-    /// Read-only: client fetches trust_score_account via RPC; no state change.
-    /// Exposed so IDL includes the "get" entrypoint; implementation is no-op.
-    pub fn get_trust_score(_ctx: Context<GetTrustScore>) -> Result<()> {
+    /// Read-only: validates freshness and confidence before a client trusts
+    /// the stored score. `allow_stale` lets a caller that explicitly accepts
+    /// stale data (Mango's "force a price no matter how stale" path) skip
+    /// the staleness check.
+    pub fn get_trust_score(
+        ctx: Context<GetTrustScore>,
+        min_confidence: u8,
+        allow_stale: bool,
+    ) -> Result<()> {
+        let account = &ctx.accounts.trust_score_account;
+        if !allow_stale {
+            let age = Clock::get()?.unix_timestamp - account.last_updated;
+            require!(
+                age <= account.max_staleness_secs,
+                TrustOracleError::StaleTrustScore
+            );
+        }
+        require!(
+            account.confidence >= min_confidence,
+            TrustOracleError::LowConfidence
+        );
+        let decayed_score = decayed_trust_score(account, Clock::get()?.unix_timestamp)?;
+        emit!(TrustScoreRead {
+            wallet: account.wallet_pubkey,
+            trust_score: decayed_score,
+            confidence: account.confidence,
+            oracle_source: account.oracle_source,
+        });
+        Ok(())
+    }
+
+    /// Bootstraps the oracle registry and sets its initial admin. Restricted
+    /// to `REGISTRY_BOOTSTRAP_AUTHORITY` so no one can front-run this call
+    /// to seize admin control of a not-yet-initialized registry.
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == REGISTRY_BOOTSTRAP_AUTHORITY,
+            TrustOracleError::UnauthorizedAdmin
+        );
+        ctx.accounts.oracle_registry.admin = ctx.accounts.authority.key();
+        Ok(())
+    }
+
+    /// Adds an oracle to the allowlist. Admin-only.
+    pub fn add_oracle(ctx: Context<ManageOracleRegistry>, oracle: Pubkey) -> Result<()> {
+        let registry = &mut ctx.accounts.oracle_registry;
+        require!(
+            registry.admin == ctx.accounts.admin.key(),
+            TrustOracleError::UnauthorizedAdmin
+        );
+
+        require!(
+            !registry.oracles.contains(&oracle),
+            TrustOracleError::OracleAlreadyRegistered
+        );
+        // Capped at MAX_SUBMISSIONS, not MAX_ORACLES: TrustScoreAccount.submissions
+        // has only MAX_SUBMISSIONS slots, so a 9th actively-submitting oracle would
+        // otherwise silently evict a still-fresh slot from the published median.
+        require!(
+            registry.oracles.len() < MAX_SUBMISSIONS,
+            TrustOracleError::OracleRegistryFull
+        );
+        registry.oracles.push(oracle);
+        Ok(())
+    }
+
+    /// Removes an oracle from the allowlist. Admin-only.
+    pub fn remove_oracle(ctx: Context<ManageOracleRegistry>, oracle: Pubkey) -> Result<()> {
+        let registry = &mut ctx.accounts.oracle_registry;
+        require!(
+            registry.admin == ctx.accounts.admin.key(),
+            TrustOracleError::UnauthorizedAdmin
+        );
+        registry.oracles.retain(|o| o != &oracle);
         Ok(())
     }
 }
 
+/// Recomputes the published trust score from the fresh, live submissions in
+/// `account`, if there are enough of them. Leaves the previous answer and
+/// `ready = false` otherwise. `status_accounts` must contain, for every
+/// populated submission slot still within `freshness_window`, the
+/// `OracleStatus` PDA address that submission's
+/// oracle derives to — `oracle_is_live` rejects the call outright if one is
+/// missing, so a submitter can't selectively omit another oracle's status
+/// account to bias which submissions count as live.
+fn publish_aggregate(
+    account: &mut TrustScoreAccount,
+    now: i64,
+    status_accounts: &[AccountInfo],
+) -> Result<()> {
+    let mut fresh: [u8; MAX_SUBMISSIONS] = [0; MAX_SUBMISSIONS];
+    let mut fresh_len = 0usize;
+    for submission in account.submissions.iter() {
+        if submission.oracle == Pubkey::default() {
+            continue;
+        }
+        if now - submission.ts > account.freshness_window {
+            continue;
+        }
+        if oracle_is_live(status_accounts, &submission.oracle, now)? {
+            fresh[fresh_len] = submission.value;
+            fresh_len += 1;
+        }
+    }
+
+    if fresh_len < account.min_submissions as usize {
+        account.ready = false;
+        return Ok(());
+    }
+
+    let median = median_of(&mut fresh[..fresh_len]);
+    account.trust_score = median;
+    account.risk_level = risk_level_for(median);
+    account.last_updated = now;
+    account.ready = true;
+    // Confidence is the share of oracle slots that reported a fresh value.
+    account.confidence = ((fresh_len * 100) / MAX_SUBMISSIONS) as u8;
+    push_history(account, median, now);
+    Ok(())
+}
+
+/// Appends `(score, ts)` to the account's rolling history ring buffer,
+/// overwriting the oldest entry once `HISTORY_LEN` samples are stored.
+fn push_history(account: &mut TrustScoreAccount, score: u8, ts: i64) {
+    let idx = account.history_cursor as usize % HISTORY_LEN;
+    account.history[idx] = (score, ts);
+    account.history_cursor = account.history_cursor.wrapping_add(1);
+}
+
+/// Computes a time-decayed trust score from `account.history`: each sample
+/// is weighted by `2^(-floor(age / half_life))`, newer samples counting for
+/// more.
+/// Falls back to the last published `trust_score` if there is no history
+/// yet. All arithmetic is checked; overflow is rejected rather than wrapped,
+/// and the final division floors rather than rounds up.
+fn decayed_trust_score(account: &TrustScoreAccount, now: i64) -> Result<u8> {
+    let half_life = if account.half_life_secs > 0 {
+        account.half_life_secs
+    } else {
+        DEFAULT_HALF_LIFE_SECS
+    };
+
+    let mut weighted_sum: u64 = 0;
+    let mut weight_total: u64 = 0;
+    for (score, ts) in account.history.iter() {
+        if *ts == 0 {
+            continue;
+        }
+        let age = now
+            .checked_sub(*ts)
+            .ok_or(TrustOracleError::MathOverflow)?
+            .max(0);
+        let halvings = u32::try_from(age / half_life).unwrap_or(u32::MAX);
+        let weight = 1u64.checked_shl(20).ok_or(TrustOracleError::MathOverflow)?;
+        let weight = weight.checked_shr(halvings).unwrap_or(0);
+
+        let contribution = weight
+            .checked_mul(*score as u64)
+            .ok_or(TrustOracleError::MathOverflow)?;
+        weighted_sum = weighted_sum
+            .checked_add(contribution)
+            .ok_or(TrustOracleError::MathOverflow)?;
+        weight_total = weight_total
+            .checked_add(weight)
+            .ok_or(TrustOracleError::MathOverflow)?;
+    }
+
+    if weight_total == 0 {
+        return Ok(account.trust_score);
+    }
+
+    let decayed = weighted_sum
+        .checked_div(weight_total)
+        .ok_or(TrustOracleError::MathOverflow)?;
+    Ok(decayed as u8)
+}
+
+/// Sorts `values` in place and returns the median, rounding down (floor) on
+/// the average of the two middle elements for even counts.
+fn median_of(values: &mut [u8]) -> u8 {
+    values.sort_unstable();
+    let len = values.len();
+    if len % 2 == 1 {
+        values[len / 2]
+    } else {
+        let lo = values[len / 2 - 1] as u16;
+        let hi = values[len / 2] as u16;
+        ((lo + hi) / 2) as u8
+    }
+}
+
+/// Whether `oracle` has heartbeated recently enough to be considered live.
+/// Derives `oracle`'s `OracleStatus` PDA address and requires that exact
+/// address to be present in `status_accounts` — an omitted or substituted
+/// account errors out rather than silently counting as live or silent, so a
+/// submitter can't pick and choose whose status gets consulted. An account
+/// present at that address but not yet initialized (never heartbeated) is
+/// treated as silent.
+fn oracle_is_live(status_accounts: &[AccountInfo], oracle: &Pubkey, now: i64) -> Result<bool> {
+    let (expected_key, _) = Pubkey::find_program_address(&[b"oracle_status", oracle.as_ref()], &ID);
+    let info = status_accounts
+        .iter()
+        .find(|info| info.key() == expected_key)
+        .ok_or(TrustOracleError::MissingOracleStatus)?;
+    Ok(Account::<OracleStatus>::try_from(info)
+        .map(|status| now - status.last_heartbeat <= status.heartbeat_interval)
+        .unwrap_or(false))
+}
+
+/// Maps a trust score to its risk bucket, per the `RiskLevel` thresholds.
+fn risk_level_for(score: u8) -> RiskLevel {
+    if score >= 70 {
+        RiskLevel::Low
+    } else if score >= 50 {
+        RiskLevel::Medium
+    } else if score >= 30 {
+        RiskLevel::High
+    } else {
+        RiskLevel::Critical
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Account structure
 // ---------------------------------------------------------------------------
@@ -48,12 +455,98 @@ pub struct TrustScoreAccount {
     pub risk_level: RiskLevel,
     /// Unix timestamp (seconds) of last update.
     pub last_updated: i64,
-    /// Oracle authority that may update this account.
+    /// Oracle authority that may update this account via `update_trust_score`.
     pub oracle_pubkey: Pubkey,
+    /// Per-oracle submission slots used by `submit_score` aggregation.
+    pub submissions: [Submission; MAX_SUBMISSIONS],
+    /// Submissions older than this (seconds) are excluded from aggregation.
+    pub freshness_window: i64,
+    /// Minimum number of fresh submissions required to publish an answer.
+    pub min_submissions: u8,
+    /// Whether `trust_score` reflects a published aggregate (enough fresh
+    /// submissions) rather than a stale or not-yet-reached answer.
+    pub ready: bool,
+    /// Confidence in `trust_score`, 0–100. Written alongside the score.
+    pub confidence: u8,
+    /// Maximum age (seconds) of `last_updated` before reads are rejected.
+    pub max_staleness_secs: i64,
+    /// Backup oracle allowed to write once the primary goes stale.
+    pub fallback_oracle: Pubkey,
+    /// Which oracle role produced the current `trust_score`.
+    pub oracle_source: OracleSource,
+    /// Rolling (score, timestamp) history, oldest entries overwritten first.
+    pub history: [(u8, i64); HISTORY_LEN],
+    /// Next slot `push_history` will write in `history`.
+    pub history_cursor: u8,
+    /// Half-life (seconds) used to time-decay `history` samples.
+    pub half_life_secs: i64,
 }
 
 impl TrustScoreAccount {
-    pub const LEN: usize = 32 + 1 + 1 + 8 + 32; // 74
+    pub const LEN: usize = 32 // wallet_pubkey
+        + 1 // trust_score
+        + 1 // risk_level
+        + 8 // last_updated
+        + 32 // oracle_pubkey
+        + Submission::LEN * MAX_SUBMISSIONS // submissions
+        + 8 // freshness_window
+        + 1 // min_submissions
+        + 1 // ready
+        + 1 // confidence
+        + 8 // max_staleness_secs
+        + 32 // fallback_oracle
+        + 1 // oracle_source
+        + (1 + 8) * HISTORY_LEN // history
+        + 1 // history_cursor
+        + 8; // half_life_secs
+}
+
+/// One oracle's reported score for a wallet, as tracked by `submit_score`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Submission {
+    /// Oracle that produced this value; `Pubkey::default()` means empty slot.
+    pub oracle: Pubkey,
+    /// Submitted score, 0–100.
+    pub value: u8,
+    /// Unix timestamp (seconds) the submission was recorded.
+    pub ts: i64,
+}
+
+impl Submission {
+    pub const LEN: usize = 32 + 1 + 8;
+}
+
+/// Allowlist of oracles permitted to write trust scores, managed by `admin`.
+#[account]
+#[derive(Default)]
+pub struct OracleRegistry {
+    /// Authority permitted to add/remove oracles.
+    pub admin: Pubkey,
+    /// Whitelisted oracle pubkeys, bounded by `MAX_ORACLES`.
+    pub oracles: Vec<Pubkey>,
+}
+
+impl OracleRegistry {
+    pub const LEN: usize = 32 // admin
+        + 4 // Vec length prefix
+        + 32 * MAX_ORACLES; // oracles
+}
+
+/// Liveness record for a single registered oracle.
+#[account]
+#[derive(Default)]
+pub struct OracleStatus {
+    /// Oracle this status tracks.
+    pub oracle: Pubkey,
+    /// Unix timestamp (seconds) of the oracle's last `heartbeat` call.
+    pub last_heartbeat: i64,
+    /// Oracle is considered silent once this many seconds pass without a
+    /// heartbeat.
+    pub heartbeat_interval: i64,
+}
+
+impl OracleStatus {
+    pub const LEN: usize = 32 + 8 + 8;
 }
 
 // ---------------------------------------------------------------------------
@@ -70,6 +563,38 @@ pub enum RiskLevel {
     Critical = 3, // score < 30
 }
 
+/// Which oracle role produced the current `trust_score`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum OracleSource {
+    #[default]
+    Primary = 0,
+    Fallback = 1,
+}
+
+// ---------------------------------------------------------------------------
+// Events
+// ---------------------------------------------------------------------------
+
+/// Emitted on every `get_trust_score` call so off-chain indexers can observe
+/// which oracle role produced the value a client just validated.
+#[event]
+pub struct TrustScoreRead {
+    pub wallet: Pubkey,
+    pub trust_score: u8,
+    pub confidence: u8,
+    pub oracle_source: OracleSource,
+}
+
+/// Emitted by `get_oracle_status` so clients can enumerate which oracles
+/// are currently live.
+#[event]
+pub struct OracleStatusRead {
+    pub oracle: Pubkey,
+    pub last_heartbeat: i64,
+    pub is_live: bool,
+}
+
 // ---------------------------------------------------------------------------
 // Instruction contexts
 // ---------------------------------------------------------------------------
@@ -80,7 +605,7 @@ pub struct UpdateTrustScore<'info> {
         init_if_needed,
         payer = oracle,
         space = 8 + TrustScoreAccount::LEN,
-        seeds = [b"trust_score", oracle.key().as_ref(), wallet.key().as_ref()],
+        seeds = [b"trust_score", wallet.key().as_ref()],
         bump
     )]
     pub trust_score_account: Account<'info, TrustScoreAccount>,
@@ -92,19 +617,126 @@ pub struct UpdateTrustScore<'info> {
     /// CHECK: Wallet pubkey; used for PDA seeds and stored in account.
     pub wallet: UncheckedAccount<'info>,
 
+    #[account(seeds = [b"oracle_registry"], bump)]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    // A fallback-oracle call must carry that oracle's `OracleStatus` PDA in
+    // `remaining_accounts`; see `oracle_is_live`.
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct GetTrustScore<'info> {
+pub struct SubmitScore<'info> {
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = 8 + TrustScoreAccount::LEN,
+        seeds = [b"trust_score", wallet.key().as_ref()],
+        bump
+    )]
+    pub trust_score_account: Account<'info, TrustScoreAccount>,
+
+    /// CHECK: Whitelisted oracle; must sign. Slot ownership is tracked in `submissions`.
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    /// CHECK: Wallet pubkey; used for PDA seeds and stored in account.
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"oracle_registry"], bump)]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    // `remaining_accounts` must carry every fresh submission's `OracleStatus`
+    // PDA, at the address that oracle derives to; see `oracle_is_live`.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OracleRegistry::LEN,
+        seeds = [b"oracle_registry"],
+        bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageOracleRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_registry"],
+        bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFallbackOracle<'info> {
     #[account(
-        seeds = [b"trust_score", oracle.key().as_ref(), wallet.key().as_ref()],
+        mut,
+        seeds = [b"trust_score", wallet.key().as_ref()],
         bump
     )]
     pub trust_score_account: Account<'info, TrustScoreAccount>,
 
+    pub oracle: Signer<'info>,
+
+    /// CHECK: Wallet pubkey; used for PDA seeds.
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"oracle_registry"], bump)]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct Heartbeat<'info> {
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = 8 + OracleStatus::LEN,
+        seeds = [b"oracle_status", oracle.key().as_ref()],
+        bump
+    )]
+    pub oracle_status: Account<'info, OracleStatus>,
+
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(seeds = [b"oracle_registry"], bump)]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetOracleStatus<'info> {
+    #[account(
+        seeds = [b"oracle_status", oracle.key().as_ref()],
+        bump
+    )]
+    pub oracle_status: Account<'info, OracleStatus>,
+
     /// CHECK: Oracle pubkey (used for PDA derivation).
     pub oracle: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetTrustScore<'info> {
+    #[account(
+        seeds = [b"trust_score", wallet.key().as_ref()],
+        bump
+    )]
+    pub trust_score_account: Account<'info, TrustScoreAccount>,
 
     /// CHECK: Wallet pubkey (used for PDA derivation).
     pub wallet: UncheckedAccount<'info>,
@@ -118,6 +750,237 @@ pub struct GetTrustScore<'info> {
 pub enum TrustOracleError {
     #[msg("Trust score must be 0–100")]
     InvalidTrustScore,
+    #[msg("Confidence must be 0–100")]
+    InvalidConfidence,
     #[msg("Only the oracle authority may update")]
     UnauthorizedOracle,
+    #[msg("Only the registry admin may perform this action")]
+    UnauthorizedAdmin,
+    #[msg("Oracle is already registered")]
+    OracleAlreadyRegistered,
+    #[msg("Oracle registry is full")]
+    OracleRegistryFull,
+    #[msg("Trust score is stale")]
+    StaleTrustScore,
+    #[msg("Trust score confidence is below the required threshold")]
+    LowConfidence,
+    #[msg("Arithmetic overflow in trust score computation")]
+    MathOverflow,
+    #[msg("A published median aggregate already exists for this wallet")]
+    ScoreAlreadyAggregated,
+    #[msg("Missing OracleStatus account for a submitting oracle")]
+    MissingOracleStatus,
+    #[msg("Fallback oracle has not heartbeated recently enough to take over writes")]
+    FallbackOracleNotLive,
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oracle_status_info<'a>(
+        key: &'a Pubkey,
+        lamports: &'a mut u64,
+        data: &'a mut [u8],
+    ) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, &ID, false, 0)
+    }
+
+    #[test]
+    fn median_of_odd_and_even_counts() {
+        assert_eq!(median_of(&mut [10, 50, 20]), 20);
+        assert_eq!(median_of(&mut [10, 20, 30, 40]), 25);
+    }
+
+    #[test]
+    fn risk_level_for_matches_published_thresholds() {
+        assert!(risk_level_for(100) == RiskLevel::Low);
+        assert!(risk_level_for(70) == RiskLevel::Low);
+        assert!(risk_level_for(69) == RiskLevel::Medium);
+        assert!(risk_level_for(50) == RiskLevel::Medium);
+        assert!(risk_level_for(49) == RiskLevel::High);
+        assert!(risk_level_for(30) == RiskLevel::High);
+        assert!(risk_level_for(29) == RiskLevel::Critical);
+        assert!(risk_level_for(0) == RiskLevel::Critical);
+    }
+
+    #[test]
+    fn decayed_trust_score_falls_back_to_trust_score_with_no_history() {
+        let account = TrustScoreAccount {
+            trust_score: 42,
+            ..Default::default()
+        };
+        assert_eq!(decayed_trust_score(&account, 1_000).unwrap(), 42);
+    }
+
+    #[test]
+    fn decayed_trust_score_weighs_recent_samples_more_than_old_ones() {
+        let mut account = TrustScoreAccount {
+            half_life_secs: 100,
+            ..Default::default()
+        };
+        // Old low sample, one half-life stale; fresh high sample just recorded.
+        account.history[0] = (20, 100);
+        account.history[1] = (80, 200);
+
+        let decayed = decayed_trust_score(&account, 200).unwrap();
+        assert!(decayed > 50, "expected recency-weighted average above 50, got {decayed}");
+    }
+
+    #[test]
+    fn oracle_is_live_checks_heartbeat_against_expected_pda() {
+        let oracle = Pubkey::new_unique();
+        let (status_key, _) =
+            Pubkey::find_program_address(&[b"oracle_status", oracle.as_ref()], &ID);
+
+        let status = OracleStatus {
+            oracle,
+            last_heartbeat: 1_000,
+            heartbeat_interval: 300,
+        };
+        let mut data = Vec::new();
+        status.try_serialize(&mut data).unwrap();
+        let mut lamports = 0u64;
+        let info = oracle_status_info(&status_key, &mut lamports, &mut data);
+
+        assert!(oracle_is_live(&[info.clone()], &oracle, 1_200).unwrap());
+        assert!(!oracle_is_live(&[info], &oracle, 2_000).unwrap());
+    }
+
+    #[test]
+    fn oracle_is_live_errors_when_expected_pda_is_not_provided() {
+        let oracle = Pubkey::new_unique();
+        let decoy = Pubkey::new_unique();
+
+        let status = OracleStatus {
+            oracle: decoy,
+            last_heartbeat: 1_000,
+            heartbeat_interval: 300,
+        };
+        let mut data = Vec::new();
+        status.try_serialize(&mut data).unwrap();
+        let mut lamports = 0u64;
+        // Wrong key: some other oracle's status account, not `oracle`'s PDA.
+        let info = oracle_status_info(&decoy, &mut lamports, &mut data);
+
+        assert!(oracle_is_live(&[info], &oracle, 1_200).is_err());
+    }
+
+    #[test]
+    fn publish_aggregate_requires_every_submitting_oracles_pda() {
+        let oracle = Pubkey::new_unique();
+        let mut account = TrustScoreAccount {
+            freshness_window: 3600,
+            min_submissions: 1,
+            ..Default::default()
+        };
+        account.submissions[0] = Submission {
+            oracle,
+            value: 55,
+            ts: 1_000,
+        };
+
+        // No status accounts at all: the submitter's liveness can't be
+        // checked, so the call must fail rather than silently excluding it.
+        assert!(publish_aggregate(&mut account, 1_000, &[]).is_err());
+    }
+
+    #[test]
+    fn publish_aggregate_publishes_median_once_quorum_of_live_oracles_reached() {
+        let now = 10_000i64;
+        let mut account = TrustScoreAccount {
+            freshness_window: 3600,
+            min_submissions: 3,
+            ..Default::default()
+        };
+
+        let oracles: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let scores = [40u8, 80, 60];
+        for (i, oracle) in oracles.iter().enumerate() {
+            account.submissions[i] = Submission {
+                oracle: *oracle,
+                value: scores[i],
+                ts: now,
+            };
+        }
+
+        let mut buffers: Vec<(Pubkey, u64, Vec<u8>)> = oracles
+            .iter()
+            .map(|oracle| {
+                let (key, _) =
+                    Pubkey::find_program_address(&[b"oracle_status", oracle.as_ref()], &ID);
+                let status = OracleStatus {
+                    oracle: *oracle,
+                    last_heartbeat: now,
+                    heartbeat_interval: 300,
+                };
+                let mut data = Vec::new();
+                status.try_serialize(&mut data).unwrap();
+                (key, 0u64, data)
+            })
+            .collect();
+        let infos: Vec<AccountInfo> = buffers
+            .iter_mut()
+            .map(|(key, lamports, data)| oracle_status_info(&*key, lamports, data))
+            .collect();
+
+        publish_aggregate(&mut account, now, &infos).unwrap();
+
+        assert!(account.ready);
+        assert_eq!(account.trust_score, 60);
+        assert_eq!(account.confidence, ((3 * 100) / MAX_SUBMISSIONS) as u8);
+    }
+
+    #[test]
+    fn publish_aggregate_leaves_ready_false_below_quorum() {
+        let now = 10_000i64;
+        let mut account = TrustScoreAccount {
+            freshness_window: 3600,
+            min_submissions: 3,
+            ready: true,
+            ..Default::default()
+        };
+
+        let oracles: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+        for (i, oracle) in oracles.iter().enumerate() {
+            account.submissions[i] = Submission {
+                oracle: *oracle,
+                value: 50,
+                ts: now,
+            };
+        }
+
+        let mut buffers: Vec<(Pubkey, u64, Vec<u8>)> = oracles
+            .iter()
+            .map(|oracle| {
+                let (key, _) =
+                    Pubkey::find_program_address(&[b"oracle_status", oracle.as_ref()], &ID);
+                let status = OracleStatus {
+                    oracle: *oracle,
+                    last_heartbeat: now,
+                    heartbeat_interval: 300,
+                };
+                let mut data = Vec::new();
+                status.try_serialize(&mut data).unwrap();
+                (key, 0u64, data)
+            })
+            .collect();
+        let infos: Vec<AccountInfo> = buffers
+            .iter_mut()
+            .map(|(key, lamports, data)| oracle_status_info(&*key, lamports, data))
+            .collect();
+
+        publish_aggregate(&mut account, now, &infos).unwrap();
+
+        assert!(!account.ready);
+    }
+
+    #[test]
+    fn oracle_registry_space_holds_every_submission_slot() {
+        assert!(MAX_SUBMISSIONS <= MAX_ORACLES);
+    }
 }